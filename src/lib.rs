@@ -1,15 +1,24 @@
 #![deny(clippy::all)]
 
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use napi_derive::napi;
 
-use grep::matcher::Matcher;
-use grep::regex::RegexMatcherBuilder;
-use grep::searcher::{BinaryDetection, SearcherBuilder};
-use grep_searcher::sinks::UTF8;
-use ignore::WalkBuilder;
+use grep::matcher::{Captures, Match, Matcher};
+use grep::pcre2::{RegexMatcher as Pcre2RegexMatcher, RegexMatcherBuilder as Pcre2RegexMatcherBuilder};
+use grep::regex::{RegexMatcher, RegexMatcherBuilder};
+use grep::searcher::{
+  BinaryDetection, Encoding, Searcher, SearcherBuilder, Sink, SinkContext, SinkMatch,
+};
+use ignore::overrides::OverrideBuilder;
+use ignore::types::TypesBuilder;
+use ignore::{WalkBuilder, WalkState};
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use serde::{Deserialize, Serialize};
 
 /// Configuration options for text search operations
@@ -38,6 +47,43 @@ pub struct SearchOptions {
   pub invert_match: Option<bool>,
   /// Maximum number of matches per file
   pub max_count: Option<u32>,
+  /// Regex engine to use: "default" (the Rust `regex` crate) or "pcre2".
+  /// PCRE2 supports lookaround and backreferences that the default
+  /// engine rejects (default: "default")
+  pub engine: Option<String>,
+  /// File types to include, e.g. `["rust", "markdown"]`. See
+  /// `get_supported_file_types` for the full registered set
+  pub types: Option<Vec<String>>,
+  /// File types to exclude, e.g. `["lock"]`
+  pub types_not: Option<Vec<String>>,
+  /// Ripgrep-style glob overrides, e.g. `["*.rs", "!vendor/**"]`
+  pub globs: Option<Vec<String>>,
+  /// Number of lines of context to show before each match
+  pub before_context: Option<u32>,
+  /// Number of lines of context to show after each match
+  pub after_context: Option<u32>,
+  /// Convenience for setting both `before_context` and `after_context`
+  /// to the same value
+  pub context: Option<u32>,
+  /// Number of threads to use for directory traversal. Defaults to the
+  /// available parallelism; set to 1 to force single-threaded traversal
+  pub threads: Option<u32>,
+  /// Skip files smaller than this size, e.g. `"10k"`, `"2M"`
+  pub min_size: Option<String>,
+  /// Skip files larger than this size, e.g. `"10k"`, `"2M"`
+  pub max_size: Option<String>,
+  /// Only search files modified within this duration, e.g. `"1week"`,
+  /// or since this date/time (RFC 3339)
+  pub changed_within: Option<String>,
+  /// Only search files modified before this duration or date/time
+  pub changed_before: Option<String>,
+  /// Text encoding to transcode from before matching, e.g. "utf-16",
+  /// "latin1", "shift_jis". Unset searches the raw bytes as UTF-8,
+  /// except for files whose BOM is detected by `auto_encoding`
+  pub encoding: Option<String>,
+  /// Sniff a leading BOM to detect the encoding, overriding `encoding`
+  /// when a BOM is present (default: true)
+  pub auto_encoding: Option<bool>,
 }
 
 impl Default for SearchOptions {
@@ -54,10 +100,213 @@ impl Default for SearchOptions {
       files_with_matches: Some(false),
       invert_match: Some(false),
       max_count: None,
+      engine: None,
+      types: None,
+      types_not: None,
+      globs: None,
+      before_context: None,
+      after_context: None,
+      context: None,
+      threads: None,
+      min_size: None,
+      max_size: None,
+      changed_within: None,
+      changed_before: None,
+      encoding: None,
+      auto_encoding: None,
     }
   }
 }
 
+/// Dispatches to either the default Rust regex engine or PCRE2.
+///
+/// `grep::regex::RegexMatcher` and `grep::pcre2::RegexMatcher` are
+/// distinct concrete types, so this enum implements `Matcher` itself
+/// and forwards `find_at`, `new_captures`, `capture_count` and
+/// `capture_index` to whichever engine is active; every other `Matcher`
+/// method uses the trait's default implementation. This keeps
+/// `Searcher::search_path` and the `find_at` loop below engine-agnostic.
+#[derive(Clone)]
+enum PatternMatcher {
+  Default(RegexMatcher),
+  Pcre2(Pcre2RegexMatcher),
+}
+
+/// `Captures` counterpart to `PatternMatcher`.
+enum PatternMatcherCaptures {
+  Default(<RegexMatcher as Matcher>::Captures),
+  Pcre2(<Pcre2RegexMatcher as Matcher>::Captures),
+}
+
+impl Captures for PatternMatcherCaptures {
+  fn len(&self) -> usize {
+    match self {
+      PatternMatcherCaptures::Default(c) => c.len(),
+      PatternMatcherCaptures::Pcre2(c) => c.len(),
+    }
+  }
+
+  fn get(&self, i: usize) -> Option<Match> {
+    match self {
+      PatternMatcherCaptures::Default(c) => c.get(i),
+      PatternMatcherCaptures::Pcre2(c) => c.get(i),
+    }
+  }
+}
+
+impl Matcher for PatternMatcher {
+  type Captures = PatternMatcherCaptures;
+  type Error = String;
+
+  fn find_at(&self, haystack: &[u8], at: usize) -> std::result::Result<Option<Match>, String> {
+    match self {
+      PatternMatcher::Default(m) => m.find_at(haystack, at).map_err(|e| e.to_string()),
+      PatternMatcher::Pcre2(m) => m.find_at(haystack, at).map_err(|e| e.to_string()),
+    }
+  }
+
+  fn new_captures(&self) -> std::result::Result<Self::Captures, String> {
+    match self {
+      PatternMatcher::Default(m) => m
+        .new_captures()
+        .map(PatternMatcherCaptures::Default)
+        .map_err(|e| e.to_string()),
+      PatternMatcher::Pcre2(m) => m
+        .new_captures()
+        .map(PatternMatcherCaptures::Pcre2)
+        .map_err(|e| e.to_string()),
+    }
+  }
+
+  fn capture_count(&self) -> usize {
+    match self {
+      PatternMatcher::Default(m) => m.capture_count(),
+      PatternMatcher::Pcre2(m) => m.capture_count(),
+    }
+  }
+
+  fn capture_index(&self, name: &str) -> Option<usize> {
+    match self {
+      PatternMatcher::Default(m) => m.capture_index(name),
+      PatternMatcher::Pcre2(m) => m.capture_index(name),
+    }
+  }
+}
+
+/// A `Sink` that records both matched and surrounding context lines.
+///
+/// `grep_searcher::sinks::UTF8` only reports matched lines, so context
+/// output (`before_context` / `after_context`) needs a real `Sink` impl
+/// that also implements `context`.
+struct ContextSink<'m, M: Matcher> {
+  matcher: &'m M,
+  file_path: &'m Path,
+  max_count: Option<u32>,
+  match_count: u32,
+  files_with_matches_only: bool,
+  matches: Vec<SearchMatch>,
+}
+
+impl<'m, M: Matcher> ContextSink<'m, M> {
+  fn line_string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+      .trim_end_matches(['\n', '\r'])
+      .to_string()
+  }
+
+  fn io_err(err: impl std::fmt::Display) -> io::Error {
+    io::Error::other(err.to_string())
+  }
+}
+
+impl<'m, M: Matcher> Sink for ContextSink<'m, M> {
+  type Error = io::Error;
+
+  fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> io::Result<bool> {
+    if let Some(max_count) = self.max_count {
+      if self.match_count >= max_count {
+        return Ok(false);
+      }
+    }
+
+    let line_number = mat.line_number().unwrap_or(0) as u32;
+    let line = Self::line_string(mat.bytes());
+    let path = self.file_path.to_string_lossy().to_string();
+
+    if self.files_with_matches_only {
+      self.matches.push(SearchMatch {
+        path,
+        line_number,
+        line,
+        start: None,
+        end: None,
+        is_context: false,
+      });
+      self.match_count += 1;
+      return Ok(false);
+    }
+
+    let line_bytes = line.as_bytes();
+    let mut start_pos = 0;
+    while let Some(found) = self
+      .matcher
+      .find_at(line_bytes, start_pos)
+      .map_err(Self::io_err)?
+    {
+      self.matches.push(SearchMatch {
+        path: path.clone(),
+        line_number,
+        line: line.clone(),
+        start: Some(found.start() as u32),
+        end: Some(found.end() as u32),
+        is_context: false,
+      });
+      self.match_count += 1;
+      start_pos = found.end();
+
+      if let Some(max_count) = self.max_count {
+        if self.match_count >= max_count {
+          return Ok(false);
+        }
+      }
+    }
+
+    Ok(true)
+  }
+
+  fn context(&mut self, _searcher: &Searcher, context: &SinkContext<'_>) -> io::Result<bool> {
+    self.matches.push(SearchMatch {
+      path: self.file_path.to_string_lossy().to_string(),
+      line_number: context.line_number().unwrap_or(0) as u32,
+      line: Self::line_string(context.bytes()),
+      start: None,
+      end: None,
+      is_context: true,
+    });
+    Ok(true)
+  }
+}
+
+/// Per-worker-thread match buffer used by the parallel walker.
+///
+/// Each worker accumulates its own matches here to avoid lock
+/// contention while searching, then flushes them into `shared` once on
+/// drop, when the worker's visitor is torn down at the end of the walk.
+struct ThreadMatchBuffer<'s> {
+  local: Vec<SearchMatch>,
+  shared: &'s Mutex<Vec<SearchMatch>>,
+}
+
+impl<'s> Drop for ThreadMatchBuffer<'s> {
+  fn drop(&mut self) {
+    if self.local.is_empty() {
+      return;
+    }
+    let mut shared = self.shared.lock().unwrap();
+    shared.append(&mut self.local);
+  }
+}
+
 /// Represents a single match found during text search
 #[derive(Debug, Serialize, Deserialize)]
 #[napi(object)]
@@ -72,6 +321,8 @@ pub struct SearchMatch {
   pub start: Option<u32>,
   /// End position of the match within the line
   pub end: Option<u32>,
+  /// Whether this line is surrounding context rather than a match itself
+  pub is_context: bool,
 }
 
 /// Complete search results with statistics and match data
@@ -92,6 +343,319 @@ pub struct SearchResult {
   pub error: Option<String>,
 }
 
+/// Aggregate statistics returned by `search_stream` once traversal
+/// completes. Matches themselves are delivered incrementally through
+/// the `on_match` callback rather than buffered here
+#[derive(Debug, Serialize, Deserialize)]
+#[napi(object)]
+pub struct SearchStats {
+  /// Total number of files searched
+  pub files_searched: u32,
+  /// Number of files containing matches
+  pub files_with_matches: u32,
+  /// Total number of individual matches found
+  pub total_matches: u32,
+  /// Whether the search completed successfully
+  pub success: bool,
+  /// Error message if search failed
+  pub error: Option<String>,
+}
+
+/// Build the `PatternMatcher` for `final_pattern` according to
+/// `opts.engine`, wiring `case_sensitive` / `multiline` through
+/// whichever concrete builder is selected
+fn build_matcher(opts: &SearchOptions, final_pattern: &str) -> Result<PatternMatcher> {
+  if opts.engine.as_deref() == Some("pcre2") {
+    let mut matcher_builder = Pcre2RegexMatcherBuilder::new();
+
+    if let Some(case_sensitive) = opts.case_sensitive {
+      matcher_builder.caseless(!case_sensitive);
+    }
+
+    if let Some(multiline) = opts.multiline {
+      matcher_builder.multi_line(multiline);
+    }
+
+    let built = matcher_builder
+      .build(final_pattern)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid regex pattern: {}", e)))?;
+    Ok(PatternMatcher::Pcre2(built))
+  } else {
+    let mut matcher_builder = RegexMatcherBuilder::new();
+
+    if let Some(case_sensitive) = opts.case_sensitive {
+      matcher_builder.case_insensitive(!case_sensitive);
+    }
+
+    if let Some(multiline) = opts.multiline {
+      matcher_builder.multi_line(multiline);
+    }
+
+    let built = matcher_builder
+      .build(final_pattern)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid regex pattern: {}", e)))?;
+    Ok(PatternMatcher::Default(built))
+  }
+}
+
+/// Build the `SearcherBuilder` shared by every search entry point
+fn build_searcher_builder(opts: &SearchOptions) -> Result<SearcherBuilder> {
+  let mut searcher_builder = SearcherBuilder::new();
+  searcher_builder.binary_detection(BinaryDetection::convert(b'\x00'));
+
+  if let Some(line_number) = opts.line_number {
+    searcher_builder.line_number(line_number);
+  }
+
+  if let Some(invert_match) = opts.invert_match {
+    searcher_builder.invert_match(invert_match);
+  }
+
+  if let Some(context) = opts.context {
+    searcher_builder.before_context(context as usize);
+    searcher_builder.after_context(context as usize);
+  }
+
+  if let Some(before_context) = opts.before_context {
+    searcher_builder.before_context(before_context as usize);
+  }
+
+  if let Some(after_context) = opts.after_context {
+    searcher_builder.after_context(after_context as usize);
+  }
+
+  if let Some(encoding) = &opts.encoding {
+    let encoding = Encoding::new(encoding)
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid encoding: {}", e)))?;
+    searcher_builder.encoding(Some(encoding));
+  }
+
+  if let Some(auto_encoding) = opts.auto_encoding {
+    searcher_builder.bom_sniffing(auto_encoding);
+  }
+
+  Ok(searcher_builder)
+}
+
+/// Build the `WalkBuilder` for `path_buf` according to `opts`
+fn build_walk_builder(path_buf: &Path, opts: &SearchOptions) -> Result<WalkBuilder> {
+  let mut walk_builder = WalkBuilder::new(path_buf);
+
+  if let Some(max_depth) = opts.max_depth {
+    walk_builder.max_depth(Some(max_depth as usize));
+  }
+
+  if let Some(hidden) = opts.hidden {
+    walk_builder.hidden(!hidden);
+  }
+
+  if let Some(follow_links) = opts.follow_links {
+    walk_builder.follow_links(follow_links);
+  }
+
+  if opts.types.is_some() || opts.types_not.is_some() {
+    let mut types_builder = TypesBuilder::new();
+    types_builder.add_defaults();
+
+    if let Some(types) = &opts.types {
+      for name in types {
+        types_builder.select(name);
+      }
+    }
+
+    if let Some(types_not) = &opts.types_not {
+      for name in types_not {
+        types_builder.negate(name);
+      }
+    }
+
+    let types = types_builder
+      .build()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid file type filter: {}", e)))?;
+    walk_builder.types(types);
+  }
+
+  if opts.globs.is_some() || opts.ignore_patterns.is_some() {
+    let mut override_builder = OverrideBuilder::new(path_buf);
+
+    if let Some(globs) = &opts.globs {
+      for glob in globs {
+        override_builder
+          .add(glob)
+          .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid glob override: {}", e)))?;
+      }
+    }
+
+    if let Some(ignore_patterns) = &opts.ignore_patterns {
+      for pattern in ignore_patterns {
+        let exclude = if pattern.starts_with('!') {
+          pattern.clone()
+        } else {
+          format!("!{}", pattern)
+        };
+        override_builder
+          .add(&exclude)
+          .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid ignore pattern: {}", e)))?;
+      }
+    }
+
+    let overrides = override_builder
+      .build()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid glob override: {}", e)))?;
+    walk_builder.overrides(overrides);
+  }
+
+  Ok(walk_builder)
+}
+
+/// Parse a byte count, accepting fd-style suffixes such as `10k`,
+/// `2M`, or `1Gi`
+fn parse_size_bytes(input: &str) -> std::result::Result<u64, String> {
+  let trimmed = input.trim();
+  let split_at = trimmed
+    .find(|c: char| !c.is_ascii_digit())
+    .unwrap_or(trimmed.len());
+  let (digits, suffix) = trimmed.split_at(split_at);
+
+  let value: u64 = digits
+    .parse()
+    .map_err(|_| format!("invalid size `{}`", input))?;
+
+  let multiplier: u64 = match suffix.to_ascii_lowercase().as_str() {
+    "" | "b" => 1,
+    "k" => 1_000,
+    "ki" => 1_024,
+    "m" => 1_000_000,
+    "mi" => 1_048_576,
+    "g" => 1_000_000_000,
+    "gi" => 1_073_741_824,
+    "t" => 1_000_000_000_000,
+    "ti" => 1_099_511_627_776,
+    _ => return Err(format!("unknown size suffix `{}` in `{}`", suffix, input)),
+  };
+
+  value
+    .checked_mul(multiplier)
+    .ok_or_else(|| format!("size `{}` overflows a 64-bit byte count", input))
+}
+
+/// Parse a `changed_within` / `changed_before` bound, accepting either
+/// an RFC 3339 date/time or a relative duration like `"1week"`
+/// (interpreted as "that long ago")
+fn parse_time_bound(input: &str) -> std::result::Result<SystemTime, String> {
+  if let Ok(time) = humantime::parse_rfc3339_weak(input) {
+    return Ok(time);
+  }
+
+  let duration = humantime::parse_duration(input).map_err(|e| e.to_string())?;
+  SystemTime::now()
+    .checked_sub(duration)
+    .ok_or_else(|| format!("duration `{}` is too large", input))
+}
+
+/// Resolved, comparison-ready form of the `min_size` / `max_size` /
+/// `changed_within` / `changed_before` options. Parsing these once up
+/// front (rather than per file) lets the parallel walker share a plain
+/// `Copy` value across worker threads instead of re-parsing, and lets
+/// an invalid filter string fail the call before any work starts
+#[derive(Debug, Clone, Copy)]
+struct MetadataFilters {
+  min_size: Option<u64>,
+  max_size: Option<u64>,
+  changed_within: Option<SystemTime>,
+  changed_before: Option<SystemTime>,
+}
+
+impl MetadataFilters {
+  fn resolve(opts: &SearchOptions) -> Result<Self> {
+    let min_size = opts
+      .min_size
+      .as_deref()
+      .map(parse_size_bytes)
+      .transpose()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid min_size: {}", e)))?;
+    let max_size = opts
+      .max_size
+      .as_deref()
+      .map(parse_size_bytes)
+      .transpose()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid max_size: {}", e)))?;
+    let changed_within = opts
+      .changed_within
+      .as_deref()
+      .map(parse_time_bound)
+      .transpose()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid changed_within: {}", e)))?;
+    let changed_before = opts
+      .changed_before
+      .as_deref()
+      .map(parse_time_bound)
+      .transpose()
+      .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid changed_before: {}", e)))?;
+
+    Ok(MetadataFilters {
+      min_size,
+      max_size,
+      changed_within,
+      changed_before,
+    })
+  }
+
+  fn is_active(&self) -> bool {
+    self.min_size.is_some()
+      || self.max_size.is_some()
+      || self.changed_within.is_some()
+      || self.changed_before.is_some()
+  }
+
+  /// Whether `entry` passes all configured bounds. Stat errors are
+  /// treated as a non-match rather than a hard failure, mirroring how
+  /// unreadable entries are skipped elsewhere in the walker
+  fn passes(&self, entry: &ignore::DirEntry) -> bool {
+    if !self.is_active() {
+      return true;
+    }
+
+    let metadata = match entry.metadata() {
+      Ok(metadata) => metadata,
+      Err(_) => return false,
+    };
+
+    if let Some(min_size) = self.min_size {
+      if metadata.len() < min_size {
+        return false;
+      }
+    }
+
+    if let Some(max_size) = self.max_size {
+      if metadata.len() > max_size {
+        return false;
+      }
+    }
+
+    if self.changed_within.is_some() || self.changed_before.is_some() {
+      let modified = match metadata.modified() {
+        Ok(modified) => modified,
+        Err(_) => return false,
+      };
+
+      if let Some(cutoff) = self.changed_within {
+        if modified < cutoff {
+          return false;
+        }
+      }
+
+      if let Some(cutoff) = self.changed_before {
+        if modified > cutoff {
+          return false;
+        }
+      }
+    }
+
+    true
+  }
+}
+
 /// Search for text patterns in multiple files and directories
 ///
 /// # Arguments
@@ -109,38 +673,16 @@ pub fn search(
 ) -> Result<SearchResult> {
   let opts = options.unwrap_or_default();
 
-  let mut matcher_builder = RegexMatcherBuilder::new();
-
-  if let Some(case_sensitive) = opts.case_sensitive {
-    matcher_builder.case_insensitive(!case_sensitive);
-  }
-
-  if let Some(multiline) = opts.multiline {
-    matcher_builder.multi_line(multiline);
-  }
-
   let final_pattern = if opts.word_regexp == Some(true) {
     format!(r"\b{}\b", pattern)
   } else {
     pattern.clone()
   };
 
-  let matcher = matcher_builder
-    .build(&final_pattern)
-    .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid regex pattern: {}", e)))?;
-
-  let mut searcher_builder = SearcherBuilder::new();
-  searcher_builder.binary_detection(BinaryDetection::convert(b'\x00'));
-
-  if let Some(line_number) = opts.line_number {
-    searcher_builder.line_number(line_number);
-  }
-
-  if let Some(invert_match) = opts.invert_match {
-    searcher_builder.invert_match(invert_match);
-  }
-
+  let matcher = build_matcher(&opts, &final_pattern)?;
+  let searcher_builder = build_searcher_builder(&opts)?;
   let mut searcher = searcher_builder.build();
+  let metadata_filters = MetadataFilters::resolve(&opts)?;
 
   let mut result = SearchResult {
     matches: Vec::new(),
@@ -160,89 +702,114 @@ pub fn search(
       continue;
     }
 
-    let mut walk_builder = WalkBuilder::new(&path_buf);
+    let mut walk_builder = build_walk_builder(&path_buf, &opts)?;
 
-    if let Some(max_depth) = opts.max_depth {
-      walk_builder.max_depth(Some(max_depth as usize));
-    }
+    let thread_count = match opts.threads {
+      Some(1) => 1,
+      Some(n) => n as usize,
+      None => std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1),
+    };
 
-    if let Some(hidden) = opts.hidden {
-      walk_builder.hidden(!hidden);
-    }
+    if thread_count <= 1 {
+      let walker = walk_builder.build();
 
-    if let Some(follow_links) = opts.follow_links {
-      walk_builder.follow_links(follow_links);
-    }
+      for entry in walker {
+        match entry {
+          Ok(entry) => {
+            if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false)
+              && metadata_filters.passes(&entry)
+            {
+              result.files_searched += 1;
 
-    let walker = walk_builder.build();
+              let file_path = entry.path();
+              let mut sink = ContextSink {
+                matcher: &matcher,
+                file_path,
+                max_count: opts.max_count,
+                match_count: 0,
+                files_with_matches_only: opts.files_with_matches == Some(true),
+                matches: Vec::new(),
+              };
 
-    for entry in walker {
-      match entry {
-        Ok(entry) => {
-          if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-            result.files_searched += 1;
-
-            let file_path = entry.path();
-            let mut file_matches = Vec::new();
-            let mut match_count = 0u32;
-
-            let search_result = searcher.search_path(
-              &matcher,
-              file_path,
-              UTF8(|lnum, line| {
-                if let Some(max_count) = opts.max_count {
-                  if match_count >= max_count {
-                    return Ok(false);
-                  }
-                }
-
-                let line_str = line;
-
-                if opts.files_with_matches == Some(true) {
-                  file_matches.push(SearchMatch {
-                    path: file_path.to_string_lossy().to_string(),
-                    line_number: lnum as u32,
-                    line: line_str.to_string(),
-                    start: None,
-                    end: None,
-                  });
-                  match_count += 1;
-                  return Ok(false);
-                } else {
-                  let mut start_pos = 0;
-                  let line_bytes = line.as_bytes();
-                  while let Some(mat) = matcher.find_at(line_bytes, start_pos)? {
-                    file_matches.push(SearchMatch {
-                      path: file_path.to_string_lossy().to_string(),
-                      line_number: lnum as u32,
-                      line: line_str.to_string(),
-                      start: Some(mat.start() as u32),
-                      end: Some(mat.end() as u32),
-                    });
-                    match_count += 1;
-                    start_pos = mat.end();
-
-                    if let Some(max_count) = opts.max_count {
-                      if match_count >= max_count {
-                        return Ok(false);
-                      }
-                    }
-                  }
-                }
-
-                Ok(true)
-              }),
-            );
-
-            if search_result.is_ok() && !file_matches.is_empty() {
-              result.files_with_matches += 1;
-              result.total_matches += file_matches.len() as u32;
-              result.matches.extend(file_matches);
+              let search_result = searcher.search_path(&matcher, file_path, &mut sink);
+
+              if search_result.is_ok() && !sink.matches.is_empty() {
+                result.files_with_matches += 1;
+                result.total_matches += sink.match_count;
+                result.matches.extend(sink.matches);
+              }
             }
           }
+          Err(_) => continue,
         }
-        Err(_) => continue,
       }
+    } else {
+      walk_builder.threads(thread_count);
+      let walker = walk_builder.build_parallel();
+
+      let shared_matches: Mutex<Vec<SearchMatch>> = Mutex::new(Vec::new());
+      let files_searched = AtomicU32::new(0);
+      let files_with_matches = AtomicU32::new(0);
+      let total_matches = AtomicU32::new(0);
+      let max_count = opts.max_count;
+      let files_with_matches_only = opts.files_with_matches == Some(true);
+
+      walker.run(|| {
+        let matcher = matcher.clone();
+        let searcher_builder = &searcher_builder;
+        let mut searcher = searcher_builder.build();
+        let files_searched = &files_searched;
+        let files_with_matches = &files_with_matches;
+        let total_matches = &total_matches;
+        let mut buffer = ThreadMatchBuffer {
+          local: Vec::new(),
+          shared: &shared_matches,
+        };
+
+        Box::new(move |entry| {
+          let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => return WalkState::Continue,
+          };
+
+          if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false)
+            || !metadata_filters.passes(&entry)
+          {
+            return WalkState::Continue;
+          }
+
+          files_searched.fetch_add(1, Ordering::Relaxed);
+
+          let file_path = entry.path();
+          let mut sink = ContextSink {
+            matcher: &matcher,
+            file_path,
+            max_count,
+            match_count: 0,
+            files_with_matches_only,
+            matches: Vec::new(),
+          };
+
+          let search_result = searcher.search_path(&matcher, file_path, &mut sink);
+
+          if search_result.is_ok() && !sink.matches.is_empty() {
+            files_with_matches.fetch_add(1, Ordering::Relaxed);
+            total_matches.fetch_add(sink.match_count, Ordering::Relaxed);
+            buffer.local.extend(sink.matches);
+          }
+
+          WalkState::Continue
+        })
+      });
+
+      result.files_searched += files_searched.load(Ordering::Relaxed);
+      result.files_with_matches += files_with_matches.load(Ordering::Relaxed);
+      result.total_matches += total_matches.load(Ordering::Relaxed);
+      result
+        .matches
+        .extend(shared_matches.into_inner().unwrap());
     }
   }
 
@@ -267,47 +834,274 @@ pub fn search_file(
   search(pattern, vec![file_path], options)
 }
 
+/// Stream matches to a JS callback instead of buffering a `SearchResult`
+///
+/// Traversal runs on a blocking worker so it doesn't stall the Node
+/// event loop. It walks serially (regardless of `SearchOptions::threads`)
+/// and buffers one file's matches at a time before handing them to
+/// `on_match`. Cancellation is best-effort: each call is queued to the
+/// JS thread without waiting for earlier calls to return, and
+/// `on_match`'s return value reaches the worker asynchronously, so a
+/// `true` response stops dispatch once the worker observes it rather
+/// than on the very next match.
+///
+/// # Arguments
+/// * `pattern` - Regular expression pattern to search for
+/// * `paths` - List of file paths or directories to search in
+/// * `on_match` - Called with each match as it is found. Return `true`
+///   from the callback to stop the search early (best-effort; matches
+///   already queued for the callback are still delivered)
+/// * `options` - Optional search configuration settings
+///
+/// # Returns
+/// Aggregate statistics once traversal completes or is cancelled
+#[napi]
+pub async fn search_stream(
+  pattern: String,
+  paths: Vec<String>,
+  on_match: ThreadsafeFunction<SearchMatch, ErrorStrategy::CalleeHandled>,
+  options: Option<SearchOptions>,
+) -> Result<SearchStats> {
+  let opts = options.unwrap_or_default();
+
+  tokio::task::spawn_blocking(move || run_search_stream(pattern, paths, opts, on_match))
+    .await
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("search_stream worker panicked: {}", e),
+      )
+    })?
+}
+
+fn run_search_stream(
+  pattern: String,
+  paths: Vec<String>,
+  opts: SearchOptions,
+  on_match: ThreadsafeFunction<SearchMatch, ErrorStrategy::CalleeHandled>,
+) -> Result<SearchStats> {
+  let final_pattern = if opts.word_regexp == Some(true) {
+    format!(r"\b{}\b", pattern)
+  } else {
+    pattern.clone()
+  };
+
+  let matcher = build_matcher(&opts, &final_pattern)?;
+  let searcher_builder = build_searcher_builder(&opts)?;
+  let mut searcher = searcher_builder.build();
+  let metadata_filters = MetadataFilters::resolve(&opts)?;
+
+  let mut stats = SearchStats {
+    files_searched: 0,
+    files_with_matches: 0,
+    total_matches: 0,
+    success: true,
+    error: None,
+  };
+
+  let stopped = Arc::new(AtomicBool::new(false));
+
+  'paths: for path in paths {
+    let path_buf = PathBuf::from(&path);
+
+    if !path_buf.exists() {
+      stats.success = false;
+      stats.error = Some(format!("Path does not exist: {}", path));
+      continue;
+    }
+
+    let walk_builder = build_walk_builder(&path_buf, &opts)?;
+    let walker = walk_builder.build();
+
+    for entry in walker {
+      if stopped.load(Ordering::Relaxed) {
+        break 'paths;
+      }
+
+      let entry = match entry {
+        Ok(entry) => entry,
+        Err(_) => continue,
+      };
+
+      if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false)
+        || !metadata_filters.passes(&entry)
+      {
+        continue;
+      }
+
+      stats.files_searched += 1;
+
+      let file_path = entry.path();
+      let mut sink = ContextSink {
+        matcher: &matcher,
+        file_path,
+        max_count: opts.max_count,
+        match_count: 0,
+        files_with_matches_only: opts.files_with_matches == Some(true),
+        matches: Vec::new(),
+      };
+
+      let search_result = searcher.search_path(&matcher, file_path, &mut sink);
+
+      if search_result.is_ok() && !sink.matches.is_empty() {
+        stats.files_with_matches += 1;
+        stats.total_matches += sink.match_count;
+
+        for search_match in sink.matches {
+          if stopped.load(Ordering::Relaxed) {
+            break;
+          }
+
+          let stopped = Arc::clone(&stopped);
+          on_match.call_with_return_value(
+            Ok(search_match),
+            ThreadsafeFunctionCallMode::Blocking,
+            move |should_stop: bool| {
+              if should_stop {
+                stopped.store(true, Ordering::Relaxed);
+              }
+              Ok(())
+            },
+          );
+        }
+      }
+    }
+  }
+
+  Ok(stats)
+}
+
 /// Validate if a regex pattern is syntactically correct
 ///
 /// # Arguments
 /// * `pattern` - Regular expression pattern to validate
+/// * `engine` - Regex engine to validate against: "default" or "pcre2"
+///   (default: "default"). A pattern using lookaround or backreferences
+///   is only valid under "pcre2"
 ///
 /// # Returns
 /// true if the pattern is valid, false otherwise
 #[napi]
-pub fn validate_pattern(pattern: String) -> bool {
-  RegexMatcherBuilder::new().build(&pattern).is_ok()
+pub fn validate_pattern(pattern: String, engine: Option<String>) -> bool {
+  if engine.as_deref() == Some("pcre2") {
+    Pcre2RegexMatcherBuilder::new().build(&pattern).is_ok()
+  } else {
+    RegexMatcherBuilder::new().build(&pattern).is_ok()
+  }
 }
 
-/// Get a list of commonly supported file extensions
+/// Get the file type names recognized by `types` / `types_not`
 ///
 /// # Returns
-/// Vector of file type names and extensions
+/// Names of the file type definitions registered by `ignore::types`,
+/// e.g. "rust", "markdown", "py". Pass any of these to
+/// `SearchOptions::types` / `types_not`
 #[napi]
 pub fn get_supported_file_types() -> Vec<String> {
-  vec![
-    "rust".to_string(),
-    "rs".to_string(),
-    "javascript".to_string(),
-    "js".to_string(),
-    "typescript".to_string(),
-    "ts".to_string(),
-    "python".to_string(),
-    "py".to_string(),
-    "go".to_string(),
-    "java".to_string(),
-    "c".to_string(),
-    "cpp".to_string(),
-    "html".to_string(),
-    "css".to_string(),
-    "json".to_string(),
-    "xml".to_string(),
-    "yaml".to_string(),
-    "yml".to_string(),
-    "toml".to_string(),
-    "markdown".to_string(),
-    "md".to_string(),
-    "text".to_string(),
-    "txt".to_string(),
-  ]
+  let mut types_builder = TypesBuilder::new();
+  types_builder.add_defaults();
+
+  let types = match types_builder.build() {
+    Ok(types) => types,
+    Err(_) => return Vec::new(),
+  };
+
+  let mut names: Vec<String> = types
+    .definitions()
+    .iter()
+    .map(|def| def.name().to_string())
+    .collect();
+  names.sort();
+  names.dedup();
+  names
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_size_bytes_plain_bytes() {
+    assert_eq!(parse_size_bytes("512").unwrap(), 512);
+  }
+
+  #[test]
+  fn parse_size_bytes_decimal_suffixes() {
+    assert_eq!(parse_size_bytes("10k").unwrap(), 10_000);
+    assert_eq!(parse_size_bytes("2M").unwrap(), 2_000_000);
+    assert_eq!(parse_size_bytes("1G").unwrap(), 1_000_000_000);
+  }
+
+  #[test]
+  fn parse_size_bytes_binary_suffixes() {
+    assert_eq!(parse_size_bytes("1Ki").unwrap(), 1_024);
+    assert_eq!(parse_size_bytes("1Mi").unwrap(), 1_048_576);
+  }
+
+  #[test]
+  fn parse_size_bytes_rejects_unknown_suffix() {
+    assert!(parse_size_bytes("10x").is_err());
+  }
+
+  #[test]
+  fn parse_size_bytes_rejects_non_numeric() {
+    assert!(parse_size_bytes("abc").is_err());
+  }
+
+  #[test]
+  fn parse_size_bytes_rejects_overflow() {
+    assert!(parse_size_bytes("18446744073709551615k").is_err());
+  }
+
+  #[test]
+  fn parse_time_bound_accepts_duration() {
+    let cutoff = parse_time_bound("1min").unwrap();
+    assert!(cutoff <= SystemTime::now());
+  }
+
+  #[test]
+  fn parse_time_bound_accepts_rfc3339() {
+    assert!(parse_time_bound("2020-01-01T00:00:00Z").is_ok());
+  }
+
+  #[test]
+  fn parse_time_bound_rejects_garbage() {
+    assert!(parse_time_bound("not a time").is_err());
+  }
+
+  #[test]
+  fn build_matcher_default_engine_finds_literal_match() {
+    let opts = SearchOptions::default();
+    let matcher = build_matcher(&opts, "needle").unwrap();
+    let found = matcher.find_at(b"a needle in a haystack", 0).unwrap();
+    assert!(found.is_some());
+  }
+
+  #[test]
+  fn build_matcher_default_engine_rejects_lookaround() {
+    let opts = SearchOptions::default();
+    assert!(build_matcher(&opts, "(?<=foo)bar").is_err());
+  }
+
+  #[test]
+  fn build_matcher_pcre2_engine_accepts_lookaround() {
+    let opts = SearchOptions {
+      engine: Some("pcre2".to_string()),
+      ..SearchOptions::default()
+    };
+    let matcher = build_matcher(&opts, "(?<=foo)bar").unwrap();
+    let found = matcher.find_at(b"foobar", 0).unwrap();
+    assert!(found.is_some());
+  }
+
+  #[test]
+  fn build_matcher_pcre2_engine_accepts_backreferences() {
+    let opts = SearchOptions {
+      engine: Some("pcre2".to_string()),
+      ..SearchOptions::default()
+    };
+    let matcher = build_matcher(&opts, r"(\w+)\s+\1").unwrap();
+    let found = matcher.find_at(b"hello hello world", 0).unwrap();
+    assert!(found.is_some());
+  }
 }